@@ -0,0 +1,281 @@
+//! Executor que roda o módulo WASM sobre um pool de instâncias, exercitando o
+//! [`wasmtime::PoolingAllocationConfig`] configurado em `main` ao invés de instanciar o
+//! módulo apenas uma vez.
+use std::sync::Mutex;
+
+use parity_scale_codec::Encode;
+use wasm_types::{Message, MemoryView, WasmPtr};
+use wasmtime::{AsContext, Caller, Engine, Extern, Instance, Linker, MemoryType, Module, Store, TypedFunc};
+
+use crate::{call_with_fuel, read_u32, write_u32, State, FUEL_BUDGET};
+
+/// Uma instância do módulo WASM pronta para processar uma `Message`, junto com
+/// as suas exports tipadas.
+struct Worker {
+    store: Store<State>,
+    call_fn: TypedFunc<u32, u32>,
+    /// Conteúdo da memória linear logo após a instanciação (já com os data
+    /// segments do módulo copiados), usado por [`Self::reset`] para restaurar
+    /// um estado limpo sem apagar esses dados constantes.
+    initial_memory: Vec<u8>,
+}
+
+impl Worker {
+    /// Limpa o estado da instância antes dela ser reusada por outra chamada:
+    /// restaura a memória para o snapshot tirado logo após a instanciação
+    /// (preservando os data segments do módulo) e descarta qualquer
+    /// entrada/saída pendente. O `fuel` é recarregado automaticamente pelo
+    /// [`call_with_fuel`] a cada chamada.
+    fn reset(&mut self) {
+        let memory = self.store.data().memory;
+        let data = memory.data_mut(&mut self.store);
+        // A memória só cresce (nunca encolhe), então o snapshot sempre cabe
+        // no prefixo atual; o que foi crescido desde a instanciação volta a
+        // zero, igual a uma página nova do WebAssembly.
+        let (initial, grown) = data.split_at_mut(self.initial_memory.len());
+        initial.copy_from_slice(&self.initial_memory);
+        grown.fill(0);
+        self.store.data_mut().pending_input.clear();
+        self.store.data_mut().pending_output = None;
+    }
+}
+
+/// Roda o módulo WASM compilado sobre um pool de instâncias, dispachando
+/// `Message`s para instâncias livres e reaproveitando-as entre chamadas.
+///
+/// É seguro chamar [`Self::dispatch`] a partir de múltiplas threads: o
+/// free-list de instâncias quentes é protegido por um [`Mutex`], e instâncias
+/// além do limite configurado são criadas sob demanda (até
+/// `MAX_INSTANCE_COUNT`, imposto pelo `PoolingAllocationConfig` da `Engine`).
+pub struct Executor {
+    engine: Engine,
+    module: Module,
+    memory_type: MemoryType,
+    /// Quantidade de instâncias ociosas mantidas "quentes" no free-list, igual
+    /// ao `max_unused_warm_slots` configurado no `PoolingAllocationConfig` da
+    /// `engine` recebida (veja [`Self::new`]) — acima desse limite a Pooling
+    /// Allocator da própria `Engine` já não mantém slots ociosos reservados,
+    /// então não faz sentido o free-list aqui guardar mais do que isso.
+    max_warm_slots: usize,
+    linker: Mutex<Linker<State>>,
+    pool: Mutex<Vec<Worker>>,
+}
+
+impl Executor {
+    /// Cria um `Executor` para o `module` fornecido, reusando a `Engine` já
+    /// configurada (com `consume_fuel` e o `PoolingAllocationConfig`) em `main`.
+    ///
+    /// `max_warm_slots` deve ser o mesmo valor passado a
+    /// `PoolingAllocationConfig::max_unused_warm_slots` ao montar `engine`.
+    pub fn new(
+        engine: Engine,
+        module: Module,
+        memory_type: MemoryType,
+        max_warm_slots: usize,
+    ) -> anyhow::Result<Self> {
+        let linker = Self::build_linker(&engine)?;
+        Ok(Self {
+            engine,
+            module,
+            memory_type,
+            max_warm_slots,
+            linker: Mutex::new(linker),
+            pool: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Monta o `Linker` com os imports `env::console_log`, `env::get_input` e
+    /// `env::set_output`, compartilhados por todas as instâncias do pool.
+    ///
+    /// `env::memory` não entra aqui: cada instância tem sua própria `Memory`,
+    /// então é definida individualmente em [`Self::spawn_worker`].
+    fn build_linker(engine: &Engine) -> anyhow::Result<Linker<State>> {
+        let mut linker = Linker::<State>::new(engine);
+
+        // `Linker::func_wrap` registra o `Func` sem prendê-lo a nenhum
+        // `Store`, ao contrário de `Func::wrap`, então a mesma definição
+        // pode ser instanciada em quantos `Store<State>` forem criados pelo
+        // pool em `Self::spawn_worker`.
+        #[allow(clippy::cast_possible_truncation)]
+        linker.func_wrap(
+            "env",
+            "console_log",
+            |caller: Caller<'_, State>, offset: u32, length: u32| {
+                let ctx = caller.as_context();
+                let view = MemoryView::new(&ctx, ctx.data().memory);
+                let string = view.read_str(offset, length)?;
+                println!("{string}");
+                Ok(())
+            },
+        )?;
+
+        linker.func_wrap(
+            "env",
+            "get_input",
+            |mut caller: Caller<'_, State>, ptr: u32, len_ptr: u32| {
+                let capacity = read_u32(&caller, len_ptr)? as usize;
+                let input = std::mem::take(&mut caller.data_mut().pending_input);
+                let copied = input.len().min(capacity);
+                let memory = caller.data().memory;
+                memory.write(&mut caller, ptr as usize, &input[..copied])?;
+                // Escreve o tamanho REAL da mensagem (não o copiado), para o
+                // guest detectar truncamento comparando com o seu buffer.
+                #[allow(clippy::cast_possible_truncation)]
+                write_u32(&mut caller, len_ptr, input.len() as u32)?;
+                Ok(())
+            },
+        )?;
+
+        linker.func_wrap(
+            "env",
+            "set_output",
+            |mut caller: Caller<'_, State>, ptr: u32, len: u32| {
+                let ctx = caller.as_context();
+                let view = MemoryView::new(&ctx, ctx.data().memory);
+                let response = view.read::<Message>(WasmPtr::new(ptr), len)?;
+                caller.data_mut().pending_output = Some(response);
+                Ok(())
+            },
+        )?;
+
+        Ok(linker)
+    }
+
+    /// Cria uma nova instância do módulo, com sua própria `Memory` e `Store`.
+    fn spawn_worker(&self) -> anyhow::Result<Worker> {
+        let mut store = State::new(&self.engine, self.memory_type.clone())?;
+        let memory = Extern::Memory(store.data().memory);
+
+        // `env::memory` é definido numa cópia do `Linker` compartilhado, já que
+        // cada instância tem a sua própria `Memory` (e o `Linker` não permite
+        // redefinir o mesmo import).
+        let mut linker = self.linker.lock().expect("linker mutex poisoned").clone();
+        linker.define(&mut store, "env", "memory", memory)?;
+        let instance: Instance = linker.instantiate(&mut store, &self.module)?;
+        drop(linker);
+
+        let call_fn = instance.get_typed_func::<u32, u32>(&mut store, "call")?;
+        // Tira o snapshot depois de instanciar, já com os data segments do
+        // módulo copiados, para `Worker::reset` restaurar entre chamadas.
+        let initial_memory = store.data().memory.data(&store).to_vec();
+        Ok(Worker { store, call_fn, initial_memory })
+    }
+
+    /// Obtém uma instância livre do pool, criando uma nova sob demanda quando
+    /// o pool está vazio.
+    fn acquire(&self) -> anyhow::Result<Worker> {
+        let popped = {
+            let mut pool = self.pool.lock().expect("pool mutex poisoned");
+            pool.pop()
+        };
+        match popped {
+            Some(worker) => Ok(worker),
+            None => self.spawn_worker(),
+        }
+    }
+
+    /// Devolve a instância ao pool para reuso, respeitando `self.max_warm_slots`.
+    fn release(&self, worker: Worker) {
+        let mut pool = self.pool.lock().expect("pool mutex poisoned");
+        if pool.len() < self.max_warm_slots {
+            pool.push(worker);
+        }
+        // Além desse limite a instância é descartada (drop) e sua memória liberada.
+    }
+
+    /// Despacha uma `Message` para uma instância livre do pool, aguarda a resposta
+    /// através de `env::set_output` e devolve a instância ao pool.
+    pub fn dispatch(&self, msg: Message) -> anyhow::Result<Message> {
+        let mut worker = self.acquire()?;
+        worker.reset();
+
+        let encoded = msg.encode();
+        let input_size = u32::try_from(encoded.len())?;
+        worker.store.data_mut().pending_input = encoded;
+
+        let dispatch_result =
+            call_with_fuel(&mut worker.store, worker.call_fn, input_size, FUEL_BUDGET);
+
+        let response = match dispatch_result {
+            Ok(_) => worker
+                .store
+                .data_mut()
+                .pending_output
+                .take()
+                .ok_or_else(|| anyhow::anyhow!("a instância não produziu nenhuma resposta")),
+            Err(err) => Err(err),
+        };
+
+        self.release(worker);
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_types::{Message, MessageText, Sender};
+    use wasmtime::{Config, Engine, MemoryType, Module};
+
+    use super::Executor;
+
+    /// Módulo WASM mínimo que ecoa de volta, via `env::set_output`, a mesma
+    /// mensagem recebida através de `env::get_input` — o suficiente para
+    /// exercitar o pool do `Executor` (incluindo `Worker::reset` entre
+    /// chamadas) sem depender do `wasm-runtime` compilado.
+    const ECHO_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 2 16))
+            (import "env" "get_input" (func $get_input (param i32 i32)))
+            (import "env" "set_output" (func $set_output (param i32 i32)))
+            (func (export "call") (param $input_size i32) (result i32)
+                (i32.store (i32.const 0) (local.get $input_size))
+                (call $get_input (i32.const 4) (i32.const 0))
+                (call $set_output (i32.const 4) (i32.load (i32.const 0)))
+                (i32.const 1)))
+    "#;
+
+    fn new_executor() -> Executor {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        config.epoch_interruption(true);
+        let engine = Engine::new(&config).expect("engine válida");
+        let module = Module::new(&engine, ECHO_WAT).expect("wat válido");
+        let memory_type = MemoryType::new(2, Some(16));
+        Executor::new(engine, module, memory_type, 4).expect("linker válido")
+    }
+
+    #[test]
+    fn dispatch_echoes_message_through_pool() {
+        let executor = new_executor();
+        let message = Message { sender: Sender::Host, message: MessageText::from("ping") };
+
+        let response = executor.dispatch(message.clone()).expect("dispatch bem-sucedido");
+        assert_eq!(response, message);
+    }
+
+    #[test]
+    fn dispatch_resets_worker_between_calls() {
+        // O mesmo `Worker` é reaproveitado entre chamadas (`max_warm_slots`
+        // > 0), e cada resposta deve ser a mensagem da própria chamada.
+        let executor = new_executor();
+        for i in 0..3 {
+            let message = Message {
+                sender: Sender::Host,
+                message: MessageText::from(format!("mensagem #{i}").as_str()),
+            };
+            let response = executor.dispatch(message.clone()).expect("dispatch bem-sucedido");
+            assert_eq!(response, message);
+        }
+
+        // Um único `Worker` foi usado, e a memória dele ainda contém as
+        // mensagens da última chamada; `Worker::reset` a restaura para o
+        // snapshot tirado logo após a instanciação.
+        let mut pool = executor.pool.lock().expect("pool mutex poisoned");
+        assert_eq!(pool.len(), 1);
+        let mut worker = pool.pop().expect("worker devolvido ao pool");
+        let memory = worker.store.data().memory;
+        assert_ne!(memory.data(&worker.store), worker.initial_memory.as_slice());
+        worker.reset();
+        assert_eq!(memory.data(&worker.store), worker.initial_memory.as_slice());
+    }
+}
@@ -1,13 +1,17 @@
 #![allow(clippy::missing_errors_doc)]
+mod executor;
 mod utils;
 
 use std::mem::MaybeUninit;
+use std::time::Duration;
 
+use executor::Executor;
 use parity_scale_codec::Encode;
-use wasm_types::{BoundedString, Kind as MessageKind, Message};
+use wasm_types::{Message, MemoryView, MessageText, Sender, WasmPtr};
 use wasmtime::{
     AsContext, Caller, Config, Engine, Extern, Func, InstanceAllocationStrategy, Linker, Memory,
-    MemoryType, Module, OptLevel, PoolingAllocationConfig, ProfilingStrategy, Store,
+    MemoryType, Module, OptLevel, PoolingAllocationConfig, ProfilingStrategy, Store, Trap,
+    TypedFunc, WasmParams, WasmResults,
 };
 
 // Código WebAssembly em formato de texto.
@@ -32,12 +36,98 @@ const MAX_MEMORY_SIZE: usize = MAX_WASM_PAGES.saturating_mul(WASM_PAGE_SIZE) as
 // Número máximo de "instancias" que podem rodar em paralelo.
 const MAX_INSTANCE_COUNT: u32 = 8;
 
+// Quantidade de instâncias ociosas mantidas "quentes", tanto no free-list do
+// `Executor` quanto no `max_unused_warm_slots` do `PoolingAllocationConfig`
+// abaixo — as duas precisam concordar, já que acima desse limite a Engine
+// também não mantém slots ociosos reservados para reaproveitar.
+const MAX_WARM_SLOTS: u32 = 4;
+
+// Orçamento de fuel (unidades de instrução) concedido a cada chamada de export.
+// Referencia: https://docs.wasmtime.dev/examples-fuel.html
+pub(crate) const FUEL_BUDGET: u64 = 10_000_000;
+
+// Intervalo entre os "ticks" do relógio de epoch, cada tick incrementa o epoch
+// da `Engine` em uma unidade.
+// Referencia: https://docs.wasmtime.dev/examples-interrupting-wasm.html
+const EPOCH_TICK_INTERVAL: Duration = Duration::from_millis(10);
+
+// Quantidade padrão de ticks que uma chamada de export pode esperar antes de
+// ser interrompida, ~1 segundo com o intervalo acima.
+const DEFAULT_EPOCH_DEADLINE_TICKS: u64 = 100;
+
+/// Erros tipados que esse runtime pode reportar durante a execução de uma instância WASM,
+/// ao invés de propagar o `anyhow::Error` opaco que o `wasmtime` retorna por baixo dos panos.
+#[derive(Debug)]
+pub enum RuntimeError {
+    /// A instância consumiu todo o `fuel` do orçamento antes de terminar a chamada.
+    OutOfFuel,
+    /// A instância excedeu o prazo de epochs concedido antes de terminar a chamada.
+    Timeout,
+}
+
+impl std::fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfFuel => {
+                f.write_str("a instância WASM ficou sem fuel antes de terminar a execução")
+            },
+            Self::Timeout => {
+                f.write_str("a instância WASM excedeu o prazo de epochs concedido")
+            },
+        }
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+/// Chama uma função exportada `func` com um orçamento de `fuel` e um prazo de epochs fixos,
+/// retornando o resultado junto com a quantidade de fuel efetivamente consumida pela chamada.
+///
+/// O `fuel` limita a quantidade de instruções executadas, o prazo de epochs limita o tempo
+/// de parede decorrido (incrementado por uma thread separada, veja `EPOCH_TICK_INTERVAL`):
+/// juntos cobrem tanto loops que computam muito quanto loops que ficam bloqueados sem
+/// consumir fuel. Quando a instância esgota um dos dois, o trap correspondente do wasmtime
+/// é convertido em [`RuntimeError::OutOfFuel`] ou [`RuntimeError::Timeout`] para que o
+/// chamador possa tratá-lo de forma tipada.
+pub(crate) fn call_with_fuel<Params, Ret>(
+    store: &mut Store<State>,
+    func: TypedFunc<Params, Ret>,
+    params: Params,
+    budget: u64,
+) -> anyhow::Result<(Ret, u64)>
+where
+    Params: WasmParams,
+    Ret: WasmResults,
+{
+    store.set_fuel(budget)?;
+    store.set_epoch_deadline(DEFAULT_EPOCH_DEADLINE_TICKS);
+    let result = func.call(&mut *store, params);
+    let remaining = store.get_fuel()?;
+    let consumed = budget.saturating_sub(remaining);
+    match result {
+        Ok(ret) => Ok((ret, consumed)),
+        Err(err) if err.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) => {
+            Err(RuntimeError::OutOfFuel.into())
+        },
+        Err(err) if err.downcast_ref::<Trap>() == Some(&Trap::Interrupt) => {
+            Err(RuntimeError::Timeout.into())
+        },
+        Err(err) => Err(err),
+    }
+}
+
 /// Estado compartilhado entre o Host e a Instância WASM
 pub struct State {
     /// Memoria que será IMPORTADA na instância WASM, a memória é criada
     /// antes da instância e precisa estar armazenada aqui para poder ser
     /// acessada dentro de funções importadas.
     pub memory: Memory,
+    /// Mensagem SCALE-encoded que o próximo `env::get_input` deve entregar ao guest.
+    pub pending_input: Vec<u8>,
+    /// Mensagem que o guest entregou através de `env::set_output`, já decodada
+    /// na hora (via [`MemoryView::read`]), aguardando ser consumida pelo host
+    /// após a chamada da export terminar.
+    pub pending_output: Option<Message>,
 }
 
 impl State {
@@ -73,6 +163,8 @@ impl State {
             // SAFETY: A memória será inicializada manualmente mais abaixo.
             #[allow(invalid_value, clippy::uninit_assumed_init)]
             memory: unsafe { MaybeUninit::<Memory>::zeroed().assume_init() },
+            pending_input: Vec::new(),
+            pending_output: None,
         };
 
         // Cria-se o `wastime::Store` com o `State`.
@@ -97,6 +189,21 @@ impl State {
     }
 }
 
+/// Lê um `u32` little-endian da memória do guest no offset `ptr`.
+pub(crate) fn read_u32(caller: &Caller<'_, State>, ptr: u32) -> anyhow::Result<u32> {
+    let ctx = caller.as_context();
+    let view = MemoryView::new(&ctx, ctx.data().memory);
+    let bytes = view.read_bytes(ptr, 4)?;
+    Ok(u32::from_le_bytes(bytes.try_into().expect("slice has exactly 4 bytes; qed")))
+}
+
+/// Escreve um `u32` little-endian na memória do guest no offset `ptr`.
+pub(crate) fn write_u32(caller: &mut Caller<'_, State>, ptr: u32, value: u32) -> anyhow::Result<()> {
+    let memory = caller.data().memory;
+    memory.write(&mut *caller, ptr as usize, &value.to_le_bytes())?;
+    Ok(())
+}
+
 fn main() -> anyhow::Result<()> {
     ////////////////////////////////////////
     // Configura o compilador WebAssembly //
@@ -111,6 +218,15 @@ fn main() -> anyhow::Result<()> {
     // Permite compilar o código usando várias threads.
     config.parallel_compilation(true);
 
+    // Ativa a contagem de `fuel`, permitindo limitar deterministicamente quantas
+    // instruções uma instância pode executar por chamada.
+    config.consume_fuel(true);
+
+    // Ativa a interrupção por epoch, um complemento ao `fuel`: enquanto o `fuel`
+    // limita instruções executadas, o epoch limita o tempo de parede decorrido,
+    // cobrindo também chamadas de host ou laços que não consomem fuel.
+    config.epoch_interruption(true);
+
     // Configura o tamanho máximo da stack para 4 megabytes.
     config.max_wasm_stack(4 * MEGABYTE);
 
@@ -138,7 +254,7 @@ fn main() -> anyhow::Result<()> {
     config.memory_guaranteed_dense_image_size(u64::MAX);
     let mut pooling_config = PoolingAllocationConfig::default();
     pooling_config
-        .max_unused_warm_slots(4)
+        .max_unused_warm_slots(MAX_WARM_SLOTS)
         //   size: 32384
         //   table_elements: 1249
         //   memory_pages: 2070
@@ -155,6 +271,16 @@ fn main() -> anyhow::Result<()> {
     // Cria a Engine usando a configuração que escolhemos.
     let engine = Engine::new(&config)?;
 
+    // Dispara uma thread que incrementa o epoch da `Engine` em intervalos regulares,
+    // o relógio de parede que limita o tempo de execução das chamadas de export.
+    {
+        let engine = engine.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(EPOCH_TICK_INTERVAL);
+            engine.increment_epoch();
+        });
+    }
+
     //////////////////////////////////
     // Compila o código WebAssembly //
     //////////////////////////////////
@@ -170,11 +296,17 @@ fn main() -> anyhow::Result<()> {
 
     // Inicia um Store, utilizado para compartilhar um estado entre
     // o host e o WebAssembly.
-    let mut store = State::new(&engine, memory_type)?;
+    let mut store = State::new(&engine, memory_type.clone())?;
 
     // Imprime o que é exportado e importado pelo WASM.
     utils::print_module_details(&module);
 
+    // Gera e imprime um skeleton de bindings Rust compiláveis para o módulo,
+    // pronto para servir de ponto de partida do glue code do host ou do guest.
+    let mut bindings = String::new();
+    utils::generate_bindings(&module, &mut bindings)?;
+    println!("{bindings}");
+
     // Define a função `console_log` que será importada e chamada pelo WebAssembly.
     // - `offset` é o endereço de memória onde a string começa, a string deve estar em formato utf-8
     // - `length` é o tamanho da string em bytes.
@@ -184,19 +316,10 @@ fn main() -> anyhow::Result<()> {
             // Recupera o contexto, que utilizaremos para ler a memória.
             let ctx = caller.as_context();
 
-            // Define o intervalo de memória que será lido.
-            let start = usize::try_from(offset).unwrap_or(usize::MAX);
-            let end = start.saturating_add(length as usize);
-
-            // Verifica se o intervalo de memória está dentro dos limites da memória.
-            let Some(bytes) = ctx.data().memory.data(&ctx).get(start..end) else {
-                anyhow::bail!("out of bounds memory access");
-            };
-
-            // Converte os bytes lidos para uma string utf-8.
-            let Ok(string) = std::str::from_utf8(bytes) else {
-                anyhow::bail!("invalid utf-8 string");
-            };
+            // Sempre re-busca o slice atual de `Memory::data`, então uma `memory.grow`
+            // entre chamadas nunca faz essa leitura observar memória liberada/movida.
+            let view = MemoryView::new(&ctx, ctx.data().memory);
+            let string = view.read_str(offset, length)?;
 
             // Imprime a string.
             println!("{string}");
@@ -205,11 +328,52 @@ fn main() -> anyhow::Result<()> {
             Ok(())
         });
 
+    // Define a função `get_input` que entrega ao guest a mensagem SCALE-encoded
+    // atualmente estagiada em `State::pending_input`.
+    // - `ptr` é o endereço onde o guest quer que a mensagem seja escrita.
+    // - `len_ptr` aponta para um `u32` que, na entrada, contém a capacidade do
+    //   buffer do guest e que, na saída, deve conter o tamanho REAL da mensagem
+    //   (não o tamanho copiado), para o guest detectar truncamento comparando o
+    //   valor retornado com o tamanho do seu próprio buffer.
+    #[allow(clippy::cast_possible_truncation)]
+    let get_input_func =
+        Func::wrap(&mut store, |mut caller: Caller<'_, State>, ptr: u32, len_ptr: u32| {
+            let capacity = read_u32(&caller, len_ptr)? as usize;
+            let input = std::mem::take(&mut caller.data_mut().pending_input);
+
+            let copied = input.len().min(capacity);
+            if input.len() > capacity {
+                println!(
+                    "aviso: get_input truncou a mensagem de {} para {copied} bytes",
+                    input.len()
+                );
+            }
+
+            let memory = caller.data().memory;
+            memory.write(&mut caller, ptr as usize, &input[..copied])?;
+            write_u32(&mut caller, len_ptr, input.len() as u32)?;
+            Ok(())
+        });
+
+    // Define a função `set_output` que recebe do guest uma `Message`
+    // SCALE-encoded de resposta, decodificada em memória através de
+    // `MemoryView::read` e guardada já pronta em `State::pending_output`.
+    let set_output_func =
+        Func::wrap(&mut store, |mut caller: Caller<'_, State>, ptr: u32, len: u32| {
+            let ctx = caller.as_context();
+            let view = MemoryView::new(&ctx, ctx.data().memory);
+            let response = view.read::<Message>(WasmPtr::new(ptr), len)?;
+            caller.data_mut().pending_output = Some(response);
+            Ok(())
+        });
+
     // Imports do módulo WebAssembly.
     let mut linker = Linker::<State>::new(&engine);
     let memory = Extern::Memory(store.data().memory);
     linker.define(&mut store, "env", "memory", memory)?;
     linker.define(&mut store, "env", "console_log", console_log_func)?;
+    linker.define(&mut store, "env", "get_input", get_input_func)?;
+    linker.define(&mut store, "env", "set_output", set_output_func)?;
 
     // Cria uma instância do módulo WebAssembly
     let instance = linker.instantiate(&mut store, &module)?;
@@ -227,9 +391,9 @@ fn main() -> anyhow::Result<()> {
     //////////////////////////
     println!("Chamando o método {export_name:?}...");
     println!("---------------------------------------------");
-    let result = run.call(&mut store, (15, 10))?;
+    let (result, fuel_consumed) = call_with_fuel(&mut store, run, (15, 10), FUEL_BUDGET)?;
     println!("---------------------------------------------");
-    println!("result = {result}\n\n");
+    println!("result = {result} (fuel consumido: {fuel_consumed})\n\n");
 
     //////////////////////////////////////////////////
     // Extrai a função `call` do módulo WebAssembly //
@@ -237,26 +401,20 @@ fn main() -> anyhow::Result<()> {
     // obs: veja o código WebAssembly em `wasm_runtime/src/lib.rs` para
     // entender como a função `add` foi definida.
     let export_name = "call";
-    let run = instance.get_typed_func::<(u32, u32), u32>(&mut store, export_name)?;
+    let run = instance.get_typed_func::<u32, u32>(&mut store, export_name)?;
 
-    // Serializa uma struct para envia-la para o WebAssembly.
-    let (offset, length) = {
-        // Serializa o tipo `Message` em um vetor de bytes
+    // Serializa a mensagem e a estagia em `State::pending_input`, de onde o guest
+    // vai busca-la através do import `env::get_input`.
+    let input_size = {
         let message =
-            Message { kind: MessageKind::Ping, message: BoundedString::from("message from host") };
+            Message { sender: Sender::Host, message: MessageText::from("message from host") };
         let encoded = message.encode();
         println!("mensagem: {message:?}");
         println!("encodada: {}", const_hex::encode_prefixed(&encoded));
 
-        // Escreve a mensagem encodada na memoria do WebAssembly
-        let ptr = 128;
-        let memory_mut = store.data().memory();
-        memory_mut.write(&mut store, ptr, &encoded)?;
-
-        // Indica onde inicia a mensagem e o seu tamanho em bytes.
-        let ptr = u32::try_from(ptr)?;
-        let len = u32::try_from(encoded.len())?;
-        (ptr, len)
+        let input_size = u32::try_from(encoded.len())?;
+        store.data_mut().pending_input = encoded;
+        input_size
     };
 
     ///////////////////////////
@@ -265,8 +423,40 @@ fn main() -> anyhow::Result<()> {
     println!();
     println!("Chamando o método {export_name:?}...");
     println!("---------------------------------------------");
-    let result = run.call(&mut store, (offset, length))?;
+    let (result, fuel_consumed) = call_with_fuel(&mut store, run, input_size, FUEL_BUDGET)?;
+    println!("---------------------------------------------");
+    println!("result = {result} (fuel consumido: {fuel_consumed})");
+
+    // Se o guest respondeu através de `env::set_output`, imprime a resposta
+    // (já decodada dentro do próprio host function, veja `set_output_func`).
+    if let Some(response) = store.data_mut().pending_output.take() {
+        println!("resposta do wasm: {response:?}");
+    }
+
+    /////////////////////////////////////////////////////////////
+    // Despacha várias mensagens em paralelo usando o Executor //
+    /////////////////////////////////////////////////////////////
+    // Ao contrário da demonstração acima, que roda uma única instância serialmente,
+    // o `Executor` mantém um pool de até `MAX_INSTANCE_COUNT` instâncias, permitindo
+    // que chamadas concorrentes de múltiplas threads sejam atendidas em paralelo.
+    println!();
+    println!("Despachando mensagens em paralelo através do Executor...");
+    println!("---------------------------------------------");
+    let executor = Executor::new(engine, module, memory_type, MAX_WARM_SLOTS as usize)?;
+    std::thread::scope(|scope| {
+        for i in 0..MAX_INSTANCE_COUNT {
+            scope.spawn(|| {
+                let message = Message {
+                    sender: Sender::Host,
+                    message: MessageText::from(format!("mensagem #{i}").as_str()),
+                };
+                match executor.dispatch(message) {
+                    Ok(response) => println!("[worker {i}] resposta: {response:?}"),
+                    Err(err) => println!("[worker {i}] erro: {err}"),
+                }
+            });
+        }
+    });
     println!("---------------------------------------------");
-    println!("result = {result}");
     Ok(())
 }
@@ -1,5 +1,6 @@
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Result as FmtResult, Write};
-use wasmtime::{ExternType, FuncType, Module, Mutability};
+use wasmtime::{ExternType, FuncType, Module, Mutability, ValType};
 
 /// Imprime informações sobre o módulo WASM, incluindo o tipos que devem ser importados e
 /// os tipos que são exportados.
@@ -81,6 +82,17 @@ fn join<T: Display, I: Iterator<Item = T>>(mut items: I, f: &mut Formatter<'_>)
     Ok(())
 }
 
+/// Um parâmetro de função com um nome sintetizado (`arg0`, `arg1`, ...), já
+/// que `wasmtime::FuncType` não carrega nomes de parâmetro, e uma declaração
+/// `fn(T)` sem nome não é Rust válido fora de um `trait`.
+struct ArgDecl(usize, RustValType);
+
+impl Display for ArgDecl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "arg{}: {}", self.0, self.1)
+    }
+}
+
 impl Display for Wasm2RustFn<'_> {
     /// Faz a formatação da função rust.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
@@ -89,7 +101,7 @@ impl Display for Wasm2RustFn<'_> {
 
         // Parametros da função.
         f.write_char('(')?;
-        join(self.func.params(), f)?;
+        join(self.func.params().map(RustValType).enumerate().map(|(i, ty)| ArgDecl(i, ty)), f)?;
         f.write_char(')')?;
 
         // Retorno da função caso exista.
@@ -99,10 +111,115 @@ impl Display for Wasm2RustFn<'_> {
         }
         if result_count > 1 {
             f.write_char('(')?;
-            join(self.func.results(), f)?;
+            join(self.func.results().map(RustValType), f)?;
             f.write_char(')')
         } else {
-            join(self.func.results(), f)
+            join(self.func.results().map(RustValType), f)
         }
     }
 }
+
+/// Nome do tipo Rust equivalente a um `ValType` do WebAssembly.
+///
+/// Tipos de referência (`funcref`/`externref`) não têm um equivalente direto
+/// em Rust puro, então são representados como um handle opaco de 32 bits.
+fn rust_type_name(ty: &ValType) -> &'static str {
+    match ty {
+        ValType::I32 => "i32",
+        ValType::I64 => "i64",
+        ValType::F32 => "f32",
+        ValType::F64 => "f64",
+        ValType::V128 => "u128",
+        _ => "u32",
+    }
+}
+
+/// Literal zero válido para inicializar uma `static` do tipo Rust equivalente
+/// a `ty`.
+fn zero_literal(ty: &ValType) -> &'static str {
+    match ty {
+        ValType::F32 | ValType::F64 => "0.0",
+        _ => "0",
+    }
+}
+
+/// Wrapper de `Display` que formata um `ValType` como o tipo Rust equivalente,
+/// ao invés do nome do tipo Wat usado pelo `Display` do próprio
+/// `wasmtime::ValType` (relevante sobretudo para `v128`, que vira `u128`).
+struct RustValType(ValType);
+
+impl Display for RustValType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(rust_type_name(&self.0))
+    }
+}
+
+/// Gera um módulo Rust compilável com os bindings do `module`: um bloco
+/// `extern "C"` por `wasm_import_module` para as funções e globals
+/// importadas, e stubs `#[no_mangle] pub extern "C"` para as funções e
+/// globals exportadas.
+///
+/// Ao contrário de [`print_module_details`], a saída é Rust válido, pronto
+/// para ser colado como ponto de partida do glue code do host ou do guest,
+/// ao invés de apenas transcrito a mão.
+pub fn generate_bindings(module: &Module, out: &mut impl Write) -> FmtResult {
+    // Agrupa as importações por `wasm_import_module`, já que cada módulo vira
+    // um bloco `extern "C"` separado.
+    let mut imports_by_module = BTreeMap::new();
+    for import in module.imports() {
+        imports_by_module.entry(import.module()).or_insert_with(Vec::new).push(import);
+    }
+
+    for (wasm_module, imports) in &imports_by_module {
+        writeln!(out, "#[link(wasm_import_module = {wasm_module:?})]")?;
+        writeln!(out, "extern \"C\" {{")?;
+        for import in imports {
+            match import.ty() {
+                ExternType::Func(func_type) => {
+                    writeln!(out, "    {};", Wasm2RustFn::fmt_fn(import.name(), func_type))?;
+                },
+                ExternType::Global(global_type) => {
+                    let keyword = match global_type.mutability() {
+                        Mutability::Const => "static",
+                        Mutability::Var => "static mut",
+                    };
+                    let ty = RustValType(global_type.content().clone());
+                    writeln!(out, "    {keyword} {}: {ty};", import.name())?;
+                },
+                // Tables, memórias e tags não têm uma declaração `extern "C"` natural.
+                ExternType::Table(_) | ExternType::Memory(_) | ExternType::Tag(_) => {},
+            }
+        }
+        writeln!(out, "}}")?;
+        writeln!(out)?;
+    }
+
+    for export in module.exports() {
+        match export.ty() {
+            ExternType::Func(func_type) => {
+                writeln!(out, "#[no_mangle]")?;
+                writeln!(out, "#[allow(unused_variables)]")?;
+                writeln!(
+                    out,
+                    "pub extern \"C\" {} {{ unimplemented!() }}",
+                    Wasm2RustFn::fmt_fn(export.name(), func_type)
+                )?;
+            },
+            ExternType::Global(global_type) => {
+                let keyword = match global_type.mutability() {
+                    Mutability::Const => "static",
+                    Mutability::Var => "static mut",
+                };
+                let ty = RustValType(global_type.content().clone());
+                let zero = zero_literal(global_type.content());
+                writeln!(out, "#[no_mangle]")?;
+                writeln!(out, "pub {keyword} {}: {ty} = {zero};", export.name())?;
+            },
+            // Tables, memórias e tags não têm uma declaração Rust natural.
+            ExternType::Table(_) | ExternType::Memory(_) | ExternType::Tag(_) => continue,
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
@@ -3,8 +3,8 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
 use const_hex as hex;
-use parity_scale_codec::Decode;
-use wasm_types::Message;
+use parity_scale_codec::{Decode, Encode};
+use wasm_types::{Message, MessageText, Sender};
 
 #[cfg(not(feature = "std"))]
 #[macro_use]
@@ -36,6 +36,8 @@ pub mod ext {
         pub fn console_log(ptr: *const u8, len: u32);
 
         pub fn get_input(ptr: *mut u8, len: &mut u32);
+
+        pub fn set_output(ptr: *const u8, len: u32);
     }
 
     #[cfg(not(target_family = "wasm"))]
@@ -53,6 +55,11 @@ pub mod ext {
     pub unsafe fn get_input(_ptr: *mut u8, len: &mut u32) {
         *len = 0;
     }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[allow(clippy::missing_safety_doc)]
+    #[allow(clippy::missing_const_for_fn)]
+    pub unsafe fn set_output(_ptr: *const u8, _len: u32) {}
 }
 
 /// Logs a message to the console.
@@ -78,6 +85,7 @@ const FAILURE: u32 = 0;
 /// Le e decoda uma struct enviada pelo Host.
 #[no_mangle]
 #[allow(clippy::missing_safety_doc)]
+#[allow(clippy::cast_possible_truncation)]
 pub unsafe extern "C" fn call(input_size: u32) -> u32 {
     // Alloca espaço na Heap
     // 8192 capacity 8192
@@ -108,6 +116,13 @@ pub unsafe extern "C" fn call(input_size: u32) -> u32 {
     // Imprime a mensagem.
     let message = format!("mensagem: {point:?}");
     log(message.as_str());
+
+    // Monta e envia uma resposta de volta para o Host através de `env::set_output`.
+    let response =
+        Message { sender: Sender::Wasm, message: MessageText::from("mensagem recebida") };
+    let encoded = response.encode();
+    ext::set_output(encoded.as_ptr(), encoded.len() as u32);
+
     OK
 }
 
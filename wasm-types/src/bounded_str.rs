@@ -1,18 +1,13 @@
 use core::{
     clone::Clone,
-    cmp::{Eq, PartialEq},
+    cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
     fmt::{Debug, Display, Formatter, Result as FmtResult},
     hash::Hash,
-    str::Chars,
+    ops::{Deref, DerefMut},
+    str::{Chars, FromStr},
 };
 use parity_scale_codec::{Decode, Encode, Error, Input};
 
-/// Max number of bytes that fits in a `BoundedString`.
-///
-/// OBS: must be more than zero and less than 256, one
-/// extra byte will be added to hold the string length.
-pub const CHAR_LIMIT: usize = 127;
-
 /// Checks that `ch` byte is the first byte in a UTF-8 code point
 /// sequence.
 #[inline]
@@ -22,21 +17,34 @@ const fn is_utf8_char_boundary(ch: u8) -> bool {
     (ch as i8) >= -0x40
 }
 
-/// Inline String up to `LEN - 1` chars long.
+/// Inline String up to `N - 1` chars long.
+///
+/// `N` is the total size in bytes of the backing buffer, the first byte
+/// holds the length, so the usable capacity is always `N - 1`.
 #[repr(transparent)]
 #[derive(Clone)]
-pub struct BoundedString {
+pub struct BoundedStr<const N: usize> {
     /// The length is the first element of the array, can't move
     /// it to another attribute, because `#[repr(transparent)]`
     /// requires an struct to have just one sized element.
-    chars: [u8; CHAR_LIMIT + 1],
+    chars: [u8; N],
 }
 
-impl BoundedString {
-    /// Creates a new empty `BoundedString`.
+/// Preserves the previous hard-coded `BoundedString` capacity for existing call sites.
+pub type BoundedString = BoundedStr<128>;
+
+impl<const N: usize> BoundedStr<N> {
+    /// Maximum number of bytes that fit in this `BoundedStr`.
+    pub const CAPACITY: usize = N - 1;
+
+    /// Enforced at construction time: `N` must leave room for the length
+    /// prefix and fit in a single `u8` length (see [`Self::CAPACITY`]).
+    const ASSERT_VALID_CAPACITY: () = assert!(N >= 1 && N <= 256, "BoundedStr: N must be between 1 and 256");
+
+    /// Creates a new empty `BoundedStr`.
     ///
-    /// Even if `BoundedString` is empty, it still consumes
-    /// `CHAR_LIMIT + 1` in the stack memory space.
+    /// Even if `BoundedStr` is empty, it still consumes
+    /// `N` bytes in the stack memory space.
     ///
     /// # Examples
     ///
@@ -47,35 +55,36 @@ impl BoundedString {
     #[inline]
     #[must_use]
     pub const fn new() -> Self {
-        Self { chars: [0u8; CHAR_LIMIT + 1] }
+        let _ = Self::ASSERT_VALID_CAPACITY;
+        Self { chars: [0u8; N] }
     }
 
-    /// Creates a `BoundedString` from `&str`, the result is always
+    /// Creates a `BoundedStr` from `&str`, the result is always
     /// a valid utf-8 string even if the provided `s` doesn't fit.
     ///
-    /// When `s.len() > CHAR_LIMIT` the exeeding bytes are ignore, only
+    /// When `s.len() > Self::CAPACITY` the exeeding bytes are ignore, only
     /// bytes that form a valid utf-8 will be considered, once a utf-8
     /// char can be up to 4 bytes long, when the max size is exceed the
-    /// final length will be between `CHAR_LIMIT-3 <= len <= CHAR_LIMIT`,
+    /// final length will be between `CAPACITY-3 <= len <= CAPACITY`,
     /// assuming `s` is also valid.
     #[must_use]
     #[allow(clippy::cast_possible_truncation)]
     pub const fn from_str(mut s: &str) -> Self {
-        if s.len() > CHAR_LIMIT {
-            // Finds the closest `i` not exceeding CHAR_LIMIT where is_char_boundary(i) is true.
-            let mut i = CHAR_LIMIT;
+        if s.len() > Self::CAPACITY {
+            // Finds the closest `i` not exceeding CAPACITY where is_char_boundary(i) is true.
+            let mut i = Self::CAPACITY;
             while i > 0 {
                 if is_utf8_char_boundary(s.as_bytes()[i]) {
                     break;
                 }
                 i -= 1;
             }
-            //  The character boundary will be within four bytes of the CHAR_LIMIT
-            debug_assert!(i >= CHAR_LIMIT.saturating_sub(3));
+            //  The character boundary will be within four bytes of the CAPACITY
+            debug_assert!(i >= Self::CAPACITY.saturating_sub(3));
             s = s.split_at(i).0;
         }
         unsafe {
-            // SAFETY: We guarantee `len` is within `CHAR_LIMIT` above.
+            // SAFETY: We guarantee `len` is within `CAPACITY` above.
             Self::from_str_unchecked(s)
         }
     }
@@ -83,18 +92,88 @@ impl BoundedString {
     #[must_use]
     #[allow(clippy::cast_possible_truncation)]
     pub const fn from_str_checked(s: &str) -> Option<Self> {
-        if s.len() > CHAR_LIMIT {
+        if s.len() > Self::CAPACITY {
             return None;
         }
         let bounded = unsafe {
-            // SAFETY: checked that `s.len() <= CHAR_LIMIT` above
+            // SAFETY: checked that `s.len() <= CAPACITY` above
             Self::from_str_unchecked(s)
         };
         Some(bounded)
     }
 
+    /// Builds a `BoundedStr` from arbitrary, possibly invalid UTF-8 bytes.
+    ///
+    /// Malformed sequences are replaced with `U+FFFD` instead of rejecting
+    /// the whole input, mirroring the standard library's lossy UTF-8
+    /// conversion. Bytes beyond `Self::CAPACITY` are dropped, same as
+    /// [`Self::from_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let s = BoundedString::from_utf8_lossy(b"hello \xFF world");
+    /// assert_eq!(s, "hello \u{FFFD} world");
+    /// ```
+    #[must_use]
+    pub fn from_utf8_lossy(bytes: &[u8]) -> Self {
+        let mut bounded = Self::new();
+        bounded.decode_lossy(bytes);
+        bounded
+    }
+
+    /// Appends as much of `bytes` as fits, replacing malformed UTF-8
+    /// sequences with `U+FFFD`. Used by [`Self::from_utf8_lossy`].
+    fn decode_lossy(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() && self.remaining_capacity() > 0 {
+            match core::str::from_utf8(bytes) {
+                Ok(valid) => {
+                    self.append_truncated(valid);
+                    break;
+                }
+                Err(err) => {
+                    let valid_up_to = err.valid_up_to();
+                    // SAFETY: `from_utf8` guarantees `bytes[..valid_up_to]` is valid UTF-8.
+                    let valid = unsafe { str::from_utf8_unchecked(&bytes[..valid_up_to]) };
+                    self.append_truncated(valid);
+                    if self.remaining_capacity() > 0 {
+                        self.try_push(char::REPLACEMENT_CHARACTER);
+                    }
+                    // `error_len() == None` means the remainder is an incomplete
+                    // sequence cut short by the end of `bytes` (e.g. a truncated
+                    // multi-byte char), not a self-contained invalid byte — the
+                    // whole remainder is consumed by the single replacement char
+                    // just pushed above, same as `String::from_utf8_lossy`.
+                    bytes = match err.error_len() {
+                        Some(error_len) => &bytes[valid_up_to + error_len..],
+                        None => &bytes[bytes.len()..],
+                    };
+                }
+            }
+        }
+    }
+
+    /// Appends as much of `s` as fits in the remaining capacity, truncated
+    /// at the closest preceding char boundary. Used by [`Self::decode_lossy`].
+    #[allow(clippy::cast_possible_truncation)]
+    fn append_truncated(&mut self, mut s: &str) {
+        let cap = self.remaining_capacity();
+        if s.len() > cap {
+            let mut i = cap;
+            while i > 0 && !is_utf8_char_boundary(s.as_bytes()[i]) {
+                i -= 1;
+            }
+            s = s.split_at(i).0;
+        }
+        unsafe {
+            // SAFETY: `s.len() <= remaining_capacity()` is guaranteed above.
+            self.append_str_unchecked(s);
+        }
+    }
+
     /// # Safety
-    /// caller must assure that `s.len() <= CHAR_LIMIT`.
+    /// caller must assure that `s.len() <= Self::CAPACITY`.
     #[must_use]
     pub const unsafe fn from_str_unchecked(s: &str) -> Self {
         let mut bounded = Self::new();
@@ -103,14 +182,14 @@ impl BoundedString {
     }
 
     /// # Safety
-    /// caller must assure that `self.len() + src.len() <= CHAR_LIMIT`.
+    /// caller must assure that `self.len() + src.len() <= Self::CAPACITY`.
     #[allow(clippy::cast_possible_truncation)]
     pub const unsafe fn append_str_unchecked(&mut self, src: &str) {
         let len = self.chars[0] as usize;
         let new_len = len.saturating_add(src.len());
-        debug_assert!(new_len <= CHAR_LIMIT);
+        debug_assert!(new_len <= Self::CAPACITY);
 
-        // Safety: Caller has to check that `s.len() + self.len() <= CHAR_LIMIT`
+        // Safety: Caller has to check that `s.len() + self.len() <= CAPACITY`
         let (_, dest) = self.chars.split_at_mut_unchecked(len + 1);
         let (dest, _) = dest.split_at_mut_unchecked(src.len());
 
@@ -172,7 +251,7 @@ impl BoundedString {
         self.as_str().chars()
     }
 
-    /// Returns the length of this `BoundedString` in bytes, not [`char`]s or
+    /// Returns the length of this `BoundedStr` in bytes, not [`char`]s or
     /// graphemes. In other words, it might not be what a human considers the
     /// length of the string.
     ///
@@ -193,7 +272,7 @@ impl BoundedString {
         self.chars[0] as usize
     }
 
-    /// Returns `true` if this `BoundedString` has a length of zero, and `false` otherwise.
+    /// Returns `true` if this `BoundedStr` has a length of zero, and `false` otherwise.
     ///
     /// # Examples
     ///
@@ -211,6 +290,22 @@ impl BoundedString {
         self.chars[0] == 0
     }
 
+    /// Returns how many more bytes can still be appended to this `BoundedStr`
+    /// before it reaches `Self::CAPACITY`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let s = BoundedString::from("abc");
+    /// assert_eq!(s.remaining_capacity(), BoundedString::CAPACITY - 3);
+    /// ```
+    #[must_use]
+    #[inline(always)]
+    pub const fn remaining_capacity(&self) -> usize {
+        Self::CAPACITY - self.len()
+    }
+
     /// Split string into length and  to the bits
     #[must_use]
     #[inline(always)]
@@ -230,7 +325,7 @@ impl BoundedString {
         unsafe { self.chars.as_ptr().add(1) }
     }
 
-    /// Returns a byte slice of this `BoundedString`'s contents.
+    /// Returns a byte slice of this `BoundedStr`'s contents.
     ///
     /// # Examples
     ///
@@ -262,7 +357,7 @@ impl BoundedString {
         unsafe { core::slice::from_raw_parts_mut(ptr, len) }
     }
 
-    /// Converts a `BoundedString` into a mutable string slice.
+    /// Converts a `BoundedStr` into a mutable string slice.
     ///
     /// # Examples
     ///
@@ -281,7 +376,7 @@ impl BoundedString {
         unsafe { str::from_utf8_unchecked_mut(self.as_bytes_mut()) }
     }
 
-    /// Extracts a string slice hold by `BoundedString`.
+    /// Extracts a string slice hold by `BoundedStr`.
     ///
     /// # Examples
     ///
@@ -298,7 +393,92 @@ impl BoundedString {
         unsafe { str::from_utf8_unchecked(bytes) }
     }
 
-    /// Try to append the given [`char`] to the end of this `BoundedString`.
+    /// Returns `true` if this `BoundedStr` contains `pat`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let s = BoundedString::from("hello world");
+    /// assert!(s.contains("world"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, pat: &str) -> bool {
+        self.as_str().contains(pat)
+    }
+
+    /// Returns `true` if this `BoundedStr` starts with `pat`.
+    #[inline]
+    #[must_use]
+    pub fn starts_with(&self, pat: &str) -> bool {
+        self.as_str().starts_with(pat)
+    }
+
+    /// Returns `true` if this `BoundedStr` ends with `pat`.
+    #[inline]
+    #[must_use]
+    pub fn ends_with(&self, pat: &str) -> bool {
+        self.as_str().ends_with(pat)
+    }
+
+    /// Returns the byte index of the first occurrence of `pat`, or [`None`]
+    /// if it doesn't occur.
+    #[inline]
+    #[must_use]
+    pub fn find(&self, pat: &str) -> Option<usize> {
+        self.as_str().find(pat)
+    }
+
+    /// Returns the byte index of the last occurrence of `pat`, or [`None`]
+    /// if it doesn't occur.
+    #[inline]
+    #[must_use]
+    pub fn rfind(&self, pat: &str) -> Option<usize> {
+        self.as_str().rfind(pat)
+    }
+
+    /// Returns an iterator over the `&str` slices separated by `pat`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let s = BoundedString::from("ping:1:2");
+    /// let mut parts = s.split(":");
+    /// assert_eq!(Some("ping"), parts.next());
+    /// assert_eq!(Some("1"), parts.next());
+    /// assert_eq!(Some("2"), parts.next());
+    /// assert_eq!(None, parts.next());
+    /// ```
+    #[inline]
+    pub fn split<'a>(&'a self, pat: &'a str) -> core::str::Split<'a, &'a str> {
+        self.as_str().split(pat)
+    }
+
+    /// Returns this `BoundedStr`'s contents with leading and trailing
+    /// whitespace removed.
+    #[inline]
+    #[must_use]
+    pub fn trim(&self) -> &str {
+        self.as_str().trim()
+    }
+
+    /// Returns this `BoundedStr`'s contents with leading whitespace removed.
+    #[inline]
+    #[must_use]
+    pub fn trim_start(&self) -> &str {
+        self.as_str().trim_start()
+    }
+
+    /// Returns this `BoundedStr`'s contents with trailing whitespace removed.
+    #[inline]
+    #[must_use]
+    pub fn trim_end(&self) -> &str {
+        self.as_str().trim_end()
+    }
+
+    /// Try to append the given [`char`] to the end of this `BoundedStr`.
     ///
     /// # Examples
     ///
@@ -324,9 +504,88 @@ impl BoundedString {
         }
     }
 
+    /// Appends the given [`char`] to the end of this `BoundedStr`, returning
+    /// `false` and leaving it unchanged if there isn't enough remaining capacity.
+    ///
+    /// This is an alias for [`Self::try_push`], named to match the builder-style
+    /// API below.
+    #[inline]
+    pub const fn push(&mut self, ch: char) -> bool {
+        self.try_push(ch)
+    }
+
+    /// Appends the given string slice to the end of this `BoundedStr`.
+    ///
+    /// Returns `false` and leaves `self` unchanged if `s` doesn't fit in the
+    /// remaining capacity, this is an all-or-nothing append, unlike [`Self::from_str`]
+    /// no partial write ever happens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let mut s = BoundedString::from("foo");
+    ///
+    /// assert!(s.push_str("bar"));
+    /// assert_eq!(s, "foobar");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn push_str(&mut self, s: &str) -> bool {
+        if s.len() > self.remaining_capacity() {
+            return false;
+        }
+        unsafe {
+            // SAFETY: just checked that `s.len() <= self.remaining_capacity()`.
+            self.append_str_unchecked(s);
+        }
+        true
+    }
+
+    /// Shortens this `BoundedStr` to the given byte length.
+    ///
+    /// If `new_len` is greater than or equal to the current length, this has no
+    /// effect. `new_len` must be a char boundary, or subsequent reads of `self`
+    /// as a `str` may produce invalid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let mut s = BoundedString::from("hello world");
+    /// s.truncate(5);
+    /// assert_eq!(s, "hello");
+    /// ```
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+        debug_assert!(self.as_str().is_char_boundary(new_len));
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            self.chars[0] = new_len as u8;
+        }
+    }
+
+    /// Truncates this `BoundedStr`, removing all contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let mut s = BoundedString::from("hello");
+    /// s.clear();
+    /// assert!(s.is_empty());
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.chars[0] = 0;
+    }
+
     /// Removes the last character from the string buffer and returns it.
     ///
-    /// Returns [`None`] if this `BoundedString` is empty.
+    /// Returns [`None`] if this `BoundedStr` is empty.
     ///
     /// # Examples
     ///
@@ -348,34 +607,146 @@ impl BoundedString {
         self.chars[0] = newlen as u8;
         Some(ch)
     }
+
+    /// Inserts `ch` at byte index `idx`, shifting the tail of the buffer to
+    /// make room.
+    ///
+    /// Returns `false` without modifying `self` if `idx` isn't a char
+    /// boundary, or if there isn't enough remaining capacity for `ch`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let mut s = BoundedString::from("ac");
+    /// assert!(s.insert(1, 'b'));
+    /// assert_eq!(s, "abc");
+    /// ```
+    #[must_use]
+    pub fn insert(&mut self, idx: usize, ch: char) -> bool {
+        let mut buf = [0u8; 4];
+        self.insert_str(idx, ch.encode_utf8(&mut buf))
+    }
+
+    /// Inserts the string slice `s` at byte index `idx`, shifting the tail
+    /// of the buffer to make room.
+    ///
+    /// Returns `false` without modifying `self` if `idx` isn't a char
+    /// boundary, or if `s` doesn't fit in the remaining capacity. This is an
+    /// all-or-nothing insert, like [`Self::push_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let mut s = BoundedString::from("ac");
+    /// assert!(s.insert_str(1, "bb"));
+    /// assert_eq!(s, "abbc");
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn insert_str(&mut self, idx: usize, s: &str) -> bool {
+        if idx > self.len() || !self.as_str().is_char_boundary(idx) {
+            return false;
+        }
+        if s.len() > self.remaining_capacity() {
+            return false;
+        }
+        let len = self.len();
+        // `+1` everywhere below accounts for the length-prefix byte at `chars[0]`.
+        let tail_start = idx + 1;
+        self.chars.copy_within(tail_start..len + 1, tail_start + s.len());
+        self.chars[tail_start..tail_start + s.len()].copy_from_slice(s.as_bytes());
+        self.chars[0] = (len + s.len()) as u8;
+        true
+    }
+
+    /// Removes the char at byte index `idx` and returns it, shifting the
+    /// tail of the buffer left to fill the gap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds or not a char boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let mut s = BoundedString::from("abc");
+    /// assert_eq!(s.remove(1), 'b');
+    /// assert_eq!(s, "ac");
+    /// ```
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn remove(&mut self, idx: usize) -> char {
+        let ch = self.as_str()[idx..]
+            .chars()
+            .next()
+            .expect("idx out of bounds of `BoundedStr`");
+        let ch_len = ch.len_utf8();
+        let len = self.len();
+        let start = idx + 1;
+        self.chars.copy_within(start + ch_len..len + 1, start);
+        self.chars[0] = (len - ch_len) as u8;
+        ch
+    }
+
+    /// Retains only the characters for which `f` returns `true`, shifting
+    /// the remaining characters left to fill any gaps, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_types::BoundedString;
+    /// let mut s = BoundedString::from("h3ll0 w0rld");
+    /// s.retain(|c| c.is_alphabetic() || c == ' ');
+    /// assert_eq!(s, "hll wrld");
+    /// ```
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn retain<F: FnMut(char) -> bool>(&mut self, mut f: F) {
+        let len = self.len();
+        let mut read = 0usize;
+        let mut new_len = 0usize;
+        while read < len {
+            let ch = self.as_str()[read..].chars().next().expect("qed; read < len");
+            let ch_len = ch.len_utf8();
+            if f(ch) {
+                if new_len != read {
+                    self.chars.copy_within(read + 1..read + 1 + ch_len, new_len + 1);
+                }
+                new_len += ch_len;
+            }
+            read += ch_len;
+        }
+        self.chars[0] = new_len as u8;
+    }
 }
 
-impl From<&'_ str> for BoundedString {
+impl<const N: usize> From<&'_ str> for BoundedStr<N> {
     #[allow(clippy::cast_possible_truncation)]
     fn from(s: &'_ str) -> Self {
         Self::from_str(s)
     }
 }
 
-impl Default for BoundedString {
+impl<const N: usize> Default for BoundedStr<N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Display for BoundedString {
+impl<const N: usize> Display for BoundedStr<N> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         <str as Display>::fmt(self.as_str(), f)
     }
 }
 
-impl Debug for BoundedString {
+impl<const N: usize> Debug for BoundedStr<N> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         <str as Debug>::fmt(self.as_str(), f)
     }
 }
 
-impl PartialEq<Self> for BoundedString {
+impl<const N: usize> PartialEq<Self> for BoundedStr<N> {
     fn eq(&self, other: &Self) -> bool {
         let a = self.as_str();
         let b = other.as_str();
@@ -383,45 +754,81 @@ impl PartialEq<Self> for BoundedString {
     }
 }
 
-impl PartialEq<str> for BoundedString {
+impl<const N: usize> PartialEq<str> for BoundedStr<N> {
     fn eq(&self, other: &str) -> bool {
         <str as PartialEq>::eq(self.as_str(), other)
     }
 }
 
-impl<'a> PartialEq<&'a str> for BoundedString {
+impl<'a, const N: usize> PartialEq<&'a str> for BoundedStr<N> {
     fn eq(&self, other: &&'a str) -> bool {
         <str as PartialEq>::eq(self.as_str(), other)
     }
 }
 
-impl Eq for BoundedString {}
+impl<const N: usize> Eq for BoundedStr<N> {}
 
-impl Hash for BoundedString {
+impl<const N: usize> Hash for BoundedStr<N> {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         <str as Hash>::hash::<H>(self.as_str(), state);
     }
 }
 
-impl AsRef<[u8]> for BoundedString {
+impl<const N: usize> PartialOrd for BoundedStr<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for BoundedStr<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        <str as Ord>::cmp(self.as_str(), other.as_str())
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for BoundedStr<N> {
     fn as_ref(&self) -> &[u8] {
         self.as_bytes()
     }
 }
 
-impl AsRef<str> for BoundedString {
+impl<const N: usize> AsRef<str> for BoundedStr<N> {
     fn as_ref(&self) -> &str {
         self.as_str()
     }
 }
 
-impl AsMut<str> for BoundedString {
+impl<const N: usize> AsMut<str> for BoundedStr<N> {
     fn as_mut(&mut self) -> &mut str {
         self.as_mut_str()
     }
 }
 
-impl Encode for BoundedString {
+impl<const N: usize> Deref for BoundedStr<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> DerefMut for BoundedStr<N> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<const N: usize> FromStr for BoundedStr<N> {
+    type Err = Error;
+
+    /// Reuses [`Self::from_str_checked`], erroring instead of truncating
+    /// when `s` doesn't fit in `Self::CAPACITY`.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::from_str_checked(s).ok_or_else(|| Error::from("string out of bounds"))
+    }
+}
+
+impl<const N: usize> Encode for BoundedStr<N> {
     #[inline(always)]
     fn size_hint(&self) -> usize {
         self.chars.len()
@@ -447,10 +854,14 @@ impl Encode for BoundedString {
     }
 }
 
-impl Decode for BoundedString {
+impl<const N: usize> Decode for BoundedStr<N> {
     fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let _ = Self::ASSERT_VALID_CAPACITY;
         let len = input.read_byte()?;
-        let mut chars = [0u8; CHAR_LIMIT + 1];
+        if len as usize > Self::CAPACITY {
+            return Err(Error::from("string out of bounds"));
+        }
+        let mut chars = [0u8; N];
         let Some((length, bytes)) = chars.split_first_mut() else {
             unreachable!("qed; chars.length > 0")
         };
@@ -466,9 +877,11 @@ impl Decode for BoundedString {
 
 #[cfg(test)]
 mod tests {
-    use super::{BoundedString, CHAR_LIMIT};
+    use super::{BoundedStr, BoundedString};
     use unicode_segmentation::UnicodeSegmentation;
 
+    const CHAR_LIMIT: usize = BoundedString::CAPACITY;
+
     #[test]
     fn it_works() {
         let tests = ["", "hello", "hello world", "a"];
@@ -482,7 +895,7 @@ mod tests {
 
     #[test]
     fn test_from_str() {
-        // Make sure `BoundedString::from_str` always parses valid utf-8 chars
+        // Make sure `BoundedStr::from_str` always parses valid utf-8 chars
         // when the provided string is greater than `CHAR_LIMIT`.
         let unicode_chars = "❤️🧡💛💚💙💜";
         assert_eq!(unicode_chars.len(), 26);
@@ -524,7 +937,7 @@ mod tests {
         // Convert `String` to `&str`
         let mut str = input.as_str();
 
-        // If we pass the whole string to `BoundedString`,
+        // If we pass the whole string to `BoundedStr`,
         // it must contain only the prefix.
         let mut bounded = BoundedString::from_str(str);
         assert_eq!(bounded, str[..CHAR_LIMIT]);
@@ -541,4 +954,35 @@ mod tests {
         }
         assert!(bounded.as_str().ends_with(unicode_chars));
     }
+
+    #[test]
+    fn from_utf8_lossy_handles_truncated_sequence() {
+        // A 2-byte prefix of a 3-byte sequence (`\u{20AC}` = `\xE2\x82\xAC`)
+        // cut short by the end of the buffer must collapse to a single
+        // replacement char, matching `String::from_utf8_lossy`.
+        let bounded = BoundedString::from_utf8_lossy(b"hi\xE2\x82");
+        assert_eq!(bounded, "hi\u{FFFD}");
+    }
+
+    #[test]
+    fn mutation_api_works() {
+        let mut s = BoundedStr::<8>::new();
+        assert_eq!(s.remaining_capacity(), 7);
+
+        assert!(s.push_str("abc"));
+        assert_eq!(s, "abc");
+        assert_eq!(s.remaining_capacity(), 4);
+
+        assert!(!s.push_str("too long"));
+        assert_eq!(s, "abc");
+
+        assert!(s.push('d'));
+        assert_eq!(s, "abcd");
+
+        s.truncate(2);
+        assert_eq!(s, "ab");
+
+        s.clear();
+        assert!(s.is_empty());
+    }
 }
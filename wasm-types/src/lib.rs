@@ -3,27 +3,35 @@
 
 mod bounded_str;
 mod sender;
+#[cfg(feature = "std")]
+mod wasm_ptr;
 
-pub use bounded_str::BoundedString;
+pub use bounded_str::{BoundedStr, BoundedString};
 use parity_scale_codec::{Decode, Encode};
 pub use sender::Sender;
+#[cfg(feature = "std")]
+pub use wasm_ptr::{MemoryError, MemoryView, WasmPtr};
+
+/// Tight capacity for [`Message::message`], short SCALE payloads don't need
+/// the full 128-byte `BoundedString` buffer.
+pub type MessageText = BoundedStr<64>;
 
 #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
 pub struct Message {
     pub sender: Sender,
-    pub message: BoundedString,
+    pub message: MessageText,
 }
 
 #[cfg(test)]
 mod tests {
     use parity_scale_codec::{DecodeAll, Encode};
 
-    use super::{BoundedString, Message, Sender};
+    use super::{Message, MessageText, Sender};
 
     #[test]
     fn encode_decode_works() {
         let message =
-            Message { sender: Sender::Wasm, message: BoundedString::from("some message") };
+            Message { sender: Sender::Wasm, message: MessageText::from("some message") };
         let encoded = message.encode();
         let decoded = Message::decode_all(&mut encoded.as_ref()).unwrap();
         assert_eq!(message, decoded);
@@ -0,0 +1,91 @@
+//! Grow-safe typed access to a WebAssembly guest's linear memory.
+//!
+//! Only available with the `std` feature, since it's bound to `wasmtime`'s
+//! host-side `Store`/`Caller` context and therefore only useful on the host.
+#![cfg(feature = "std")]
+
+use core::marker::PhantomData;
+use parity_scale_codec::{Decode, Error as CodecError};
+use wasmtime::{AsContext, Memory};
+
+/// A typed pointer into a WebAssembly guest's linear memory.
+///
+/// Carries no borrow of the memory itself, only the `u32` offset guest code
+/// would pass across the host/guest boundary; reading through it requires a
+/// [`MemoryView`].
+#[derive(Debug, Clone, Copy)]
+pub struct WasmPtr<T> {
+    offset: u32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> WasmPtr<T> {
+    #[must_use]
+    pub const fn new(offset: u32) -> Self {
+        Self { offset, _marker: PhantomData }
+    }
+
+    #[must_use]
+    pub const fn offset(self) -> u32 {
+        self.offset
+    }
+}
+
+/// Errors produced while reading guest memory through a [`MemoryView`].
+#[derive(Debug)]
+pub enum MemoryError {
+    /// The requested range falls outside the memory's current size.
+    OutOfBounds,
+    /// The requested range isn't valid UTF-8.
+    InvalidUtf8,
+    /// SCALE-decoding the requested range failed.
+    Decode(CodecError),
+}
+
+impl core::fmt::Display for MemoryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OutOfBounds => f.write_str("out of bounds memory access"),
+            Self::InvalidUtf8 => f.write_str("invalid utf-8 string"),
+            Self::Decode(err) => write!(f, "failed to decode value from guest memory: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for MemoryError {}
+
+/// Bounds-checked view over a [`wasmtime::Memory`], bound to a `Store`/`Caller` context `C`.
+///
+/// Every access re-derives the byte slice from `Memory::data(ctx)` instead of caching it
+/// across calls, so it can never observe a freed/moved backing store after a `memory.grow`.
+pub struct MemoryView<'ctx, C> {
+    ctx: &'ctx C,
+    memory: Memory,
+}
+
+impl<'ctx, C: AsContext> MemoryView<'ctx, C> {
+    #[must_use]
+    pub const fn new(ctx: &'ctx C, memory: Memory) -> Self {
+        Self { ctx, memory }
+    }
+
+    /// Reads `len` bytes starting at `offset`, bounds-checked against the
+    /// memory's *current* size.
+    pub fn read_bytes(&self, offset: u32, len: u32) -> Result<&[u8], MemoryError> {
+        let start = usize::try_from(offset).unwrap_or(usize::MAX);
+        let end = start.saturating_add(len as usize);
+        self.memory.data(self.ctx).get(start..end).ok_or(MemoryError::OutOfBounds)
+    }
+
+    /// Reads `len` bytes at `offset` and interprets them as a UTF-8 string slice.
+    pub fn read_str(&self, offset: u32, len: u32) -> Result<&str, MemoryError> {
+        let bytes = self.read_bytes(offset, len)?;
+        core::str::from_utf8(bytes).map_err(|_| MemoryError::InvalidUtf8)
+    }
+
+    /// Reads and SCALE-decodes a `T` encoded in `len` bytes at `ptr`.
+    pub fn read<T: Decode>(&self, ptr: WasmPtr<T>, len: u32) -> Result<T, MemoryError> {
+        let bytes = self.read_bytes(ptr.offset(), len)?;
+        T::decode(&mut &*bytes).map_err(MemoryError::Decode)
+    }
+}